@@ -4,6 +4,9 @@
 //=======================================================================//
 
 use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    iter::Peekable,
     ops::RangeInclusive,
     path::{Path, PathBuf}
 };
@@ -15,6 +18,10 @@ use std::{
 
 /// The range of the possible draw heights of a texture.
 pub const TEXTURE_HEIGHT_RANGE: RangeInclusive<i8> = 0..=20;
+/// The label of the lowest texture draw height.
+pub const TEXTURE_HEIGHT_FLOOR_LABEL: &str = "Floor";
+/// The label of the highest texture draw height.
+pub const TEXTURE_HEIGHT_CEILING_LABEL: &str = "Ceiling";
 /// The file extension of the main HillVacuum file format.
 #[allow(clippy::doc_markdown)]
 pub const FILE_EXTENSION: &str = "hv";
@@ -35,9 +42,543 @@ where
     #[inline]
     #[must_use]
     fn next_value(&mut self) -> T { self.next().unwrap() }
+
+    /// Returns the first unwrapped value. Same as [`NextValue::next_value`], for call sites where
+    /// the iterator is fresh.
+    /// ```
+    /// # use hill_vacuum_shared::NextValue;
+    /// assert_eq!([1, 2].iter().first_value(), [1, 2].iter().next_value());
+    /// ```
+    /// # Panics
+    /// Panic occurs if the next value is None.
+    #[inline]
+    #[must_use]
+    fn first_value(&mut self) -> T { self.next_value() }
+
+    /// Calls `f` on each value, stopping at the first [`Err`] and returning it.
+    /// # Errors
+    /// Returns the first error returned by `f`.
+    #[inline]
+    fn for_each_value<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        Self: Sized,
+        F: FnMut(T) -> Result<(), E>
+    {
+        for value in self
+        {
+            f(value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the iterator and returns the last unwrapped value.
+    /// # Panics
+    /// Panic occurs if the iterator is empty.
+    #[inline]
+    #[must_use]
+    fn last_value(self) -> T
+    where
+        Self: Sized
+    {
+        self.last().unwrap()
+    }
+
+    /// Consumes the iterator and returns how many values map to each of the keys returned by `key`.
+    #[inline]
+    #[must_use]
+    fn counts_by<K, F>(self, mut key: F) -> HashMap<K, usize>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+        F: FnMut(T) -> K
+    {
+        let mut counts = HashMap::new();
+
+        for value in self
+        {
+            *counts.entry(key(value)).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Pulls the next value and checks whether it is equal to `expected`.
+    /// # Errors
+    /// Returns `err` if the next value is not equal to `expected` or the iterator is empty.
+    #[inline]
+    fn next_value_expect_eq<E>(&mut self, expected: T, err: E) -> Result<(), E>
+    where
+        Self: Sized,
+        T: PartialEq
+    {
+        match self.next()
+        {
+            Some(value) if value == expected => Ok(()),
+            _ => Err(err)
+        }
+    }
+
+    /// Returns the only value of the iterator, if any.
+    /// # Errors
+    /// Returns [`TooMany`] if the iterator contains more than one value.
+    #[inline]
+    fn at_most_one(&mut self) -> Result<Option<T>, TooMany>
+    {
+        let value = return_if_none!(self.next(), Ok(None));
+
+        if self.next().is_some()
+        {
+            return Err(TooMany);
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Consumes the iterator and returns the first value that is equal to a previous one, if any.
+    #[inline]
+    #[must_use]
+    fn find_duplicate(mut self) -> Option<T>
+    where
+        Self: Sized,
+        T: Eq + Hash + Clone
+    {
+        let mut values = HashSet::new();
+        self.find(|value| !values.insert(value.clone()))
+    }
+
+    /// Returns the next unwrapped value converted into `U`.
+    /// # Panics
+    /// Panic occurs if the next value is None.
+    /// # Errors
+    /// Returns the conversion error if the value cannot be converted into `U`.
+    #[inline]
+    fn next_value_try_into<U>(&mut self) -> Result<U, U::Error>
+    where
+        Self: Sized,
+        U: TryFrom<T>
+    {
+        U::try_from(self.next_value())
+    }
+
+    /// Returns the value with the minimum key returned by `key`.
+    /// # Panics
+    /// Panic occurs if the iterator is empty.
+    #[inline]
+    #[must_use]
+    fn min_by_key_value<K, F>(&mut self, key: F) -> T
+    where
+        Self: Sized,
+        K: Ord,
+        F: FnMut(&T) -> K
+    {
+        self.min_by_key(key).unwrap()
+    }
+
+    /// Returns the next unwrapped value.
+    /// # Panics
+    /// Panic occurs if the next value is None or it is equal to the default value of `T`.
+    #[inline]
+    #[must_use]
+    fn next_value_non_default(&mut self) -> T
+    where
+        T: Default + PartialEq
+    {
+        let value = self.next_value();
+        assert!(value != T::default(), "Next value is the default value.");
+        value
+    }
+
+    /// Consumes the iterator and returns the [`Vec`] of the leading values that satisfy `pred`.
+    /// Since this relies on [`Iterator::take_while`], the first value not satisfying `pred` is
+    /// consumed as well.
+    #[inline]
+    #[must_use]
+    fn take_while_collect<F>(self, pred: F) -> Vec<T>
+    where
+        Self: Sized,
+        F: FnMut(&T) -> bool
+    {
+        self.take_while(pred).collect()
+    }
+
+    /// Collects all the remaining values and checks that their amount is within `min..=max`.
+    /// # Errors
+    /// Returns the amount of collected values if it is outside `min..=max`.
+    #[inline]
+    fn collect_bounded(&mut self, min: usize, max: usize) -> Result<Vec<T>, usize>
+    {
+        let values = self.collect::<Vec<_>>();
+
+        if (min..=max).contains(&values.len())
+        {
+            Ok(values)
+        }
+        else
+        {
+            Err(values.len())
+        }
+    }
+
+    /// Consumes the iterator and returns an iterator yielding a [`Vec`] for each run of consecutive
+    /// values with equal keys returned by `key`.
+    #[inline]
+    fn chunk_by_key<K, F>(self, mut key: F) -> impl Iterator<Item = Vec<T>>
+    where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&T) -> K
+    {
+        let mut iter = self.peekable();
+
+        std::iter::from_fn(move || {
+            let first = iter.next()?;
+            let run_key = key(&first);
+            let mut run = vec![first];
+
+            while let Some(value) = iter.next_if(|value| key(value) == run_key)
+            {
+                run.push(value);
+            }
+
+            Some(run)
+        })
+    }
+
+    /// Returns the next unwrapped value if it is within `range`.
+    /// # Panics
+    /// Panic occurs if the next value is None.
+    /// # Errors
+    /// Returns the next value if it is outside `range`.
+    #[inline]
+    fn next_value_within(&mut self, range: RangeInclusive<T>) -> Result<T, T>
+    where
+        T: PartialOrd
+    {
+        let value = self.next_value();
+
+        if range.contains(&value)
+        {
+            Ok(value)
+        }
+        else
+        {
+            Err(value)
+        }
+    }
+
+    /// Consumes the iterator and returns the sum of the keys returned by `key`.
+    #[inline]
+    #[must_use]
+    fn sum_by_key<K, F>(self, key: F) -> K
+    where
+        Self: Sized,
+        K: std::iter::Sum,
+        F: FnMut(T) -> K
+    {
+        self.map(key).sum()
+    }
+
+    /// Consumes the iterator and returns an iterator yielding the sum of each value with all the
+    /// previous ones.
+    #[inline]
+    fn running_total(self) -> impl Iterator<Item = T>
+    where
+        Self: Sized,
+        T: std::ops::Add<Output = T> + Copy + Default
+    {
+        self.scan(T::default(), |total, value| {
+            *total = *total + value;
+            Some(*total)
+        })
+    }
+
+    /// Returns an array filled with the next `N` values, using the default value of `T` for the
+    /// missing ones if the iterator is exhausted.
+    #[inline]
+    #[must_use]
+    fn collect_array_or_default<const N: usize>(&mut self) -> [T; N]
+    where
+        Self: Sized,
+        T: Default
+    {
+        let mut iter = self.fuse();
+        std::array::from_fn(|_| iter.next().unwrap_or_default())
+    }
+
+    /// Returns the next unwrapped value, tracing it under `target` if the `log` feature is enabled.
+    /// # Panics
+    /// Panic occurs if the next value is None.
+    #[inline]
+    #[must_use]
+    #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+    fn next_value_logged(&mut self, target: &str) -> T
+    where
+        T: std::fmt::Debug
+    {
+        let value = self.next_value();
+        #[cfg(feature = "log")]
+        log::trace!(target: target, "{value:?}");
+        value
+    }
+
+    /// Returns the index and the value of the first value satisfying `pred`.
+    /// # Panics
+    /// Panic occurs if no value satisfies `pred`.
+    #[inline]
+    #[must_use]
+    fn find_indexed_value<F>(&mut self, mut pred: F) -> (usize, T)
+    where
+        Self: Sized,
+        F: FnMut(&T) -> bool
+    {
+        self.enumerate().find(|(_, value)| pred(value)).unwrap()
+    }
+
+    /// Returns the next unwrapped value.
+    /// # Panics
+    /// Panic occurs if the next value is None or it does not satisfy `pred`, in which case the
+    /// panic message is `msg`.
+    #[inline]
+    #[must_use]
+    fn next_value_checked<F>(&mut self, pred: F, msg: &str) -> T
+    where
+        Self: Sized,
+        F: FnOnce(&T) -> bool
+    {
+        let value = self.next_value();
+        assert!(pred(&value), "{msg}");
+        value
+    }
+
+    /// Returns the [`Vec`] of the values preceding the first one equal to `sentinel`, which is
+    /// consumed.
+    /// # Panics
+    /// Panic occurs if no value is equal to `sentinel`.
+    #[inline]
+    #[must_use]
+    fn collect_until(&mut self, sentinel: T) -> Vec<T>
+    where
+        T: PartialEq
+    {
+        let mut values = Vec::new();
+
+        loop
+        {
+            let value = self.next_value();
+
+            if value == sentinel
+            {
+                return values;
+            }
+
+            values.push(value);
+        }
+    }
+
+    /// Returns the only value of the iterator.
+    /// # Panics
+    /// Panic occurs if the iterator is empty or contains more than one value.
+    #[inline]
+    #[must_use]
+    fn exactly_one_value(&mut self) -> T
+    {
+        let value = self.next_value();
+        assert!(self.next().is_none(), "Iterator contains more than one value.");
+        value
+    }
+
+    /// Returns the [`Vec`] of the next `pairs` pairs of values.
+    /// # Panics
+    /// Panic occurs if the iterator contains less than `2 * pairs` values.
+    #[inline]
+    #[must_use]
+    fn collect_pairs_exact(&mut self, pairs: usize) -> Vec<(T, T)>
+    {
+        (0..pairs).map(|_| (self.next_value(), self.next_value())).collect()
+    }
+
+    /// Returns the maximum value.
+    /// # Panics
+    /// Panic occurs if the iterator is empty.
+    #[inline]
+    #[must_use]
+    fn max_value(&mut self) -> T
+    where
+        T: Ord
+    {
+        self.max().unwrap()
+    }
+
+    /// Returns the next unwrapped value, `index` being the position of the value in the caller's
+    /// loop.
+    /// # Panics
+    /// Panic occurs if the next value is None, with a message containing `index`.
+    #[inline]
+    #[must_use]
+    fn next_value_at(&mut self, index: usize) -> T
+    {
+        self.next()
+            .unwrap_or_else(|| panic!("No next value at index {index}."))
+    }
+
+    /// Consumes the iterator and returns an iterator yielding each value with the length of its run
+    /// of consecutive equal values.
+    #[inline]
+    fn run_length_encode(self) -> impl Iterator<Item = (T, usize)>
+    where
+        Self: Sized,
+        T: PartialEq
+    {
+        let mut iter = self.peekable();
+
+        std::iter::from_fn(move || {
+            let value = iter.next()?;
+            let mut len = 1;
+
+            while iter.next_if_eq(&value).is_some()
+            {
+                len += 1;
+            }
+
+            Some((value, len))
+        })
+    }
+
+    /// Consumes the iterator and returns a [`PeekValueIter`] wrapping it.
+    /// ```
+    /// # use hill_vacuum_shared::NextValue;
+    /// let mut iter = [1, 2].into_iter().into_peek_value();
+    /// assert_eq!(*iter.peek_value(), 1);
+    /// assert_eq!(iter.next_value(), 1);
+    /// assert_eq!(*iter.peek_value(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    fn into_peek_value(self) -> PeekValueIter<Self>
+    where
+        Self: Sized
+    {
+        PeekValueIter(self.peekable())
+    }
+}
+
+impl<T, U: Iterator<Item = T> + ?Sized> NextValue<T> for U {}
+
+//=======================================================================//
+
+/// A trait for double ended iterators to get values from the back and immediately unwrap them.
+pub trait NextValueBack<T>
+where
+    Self: DoubleEndedIterator<Item = T>
+{
+    /// Returns the unwrapped `n`th value from the back.
+    /// # Panics
+    /// Panic occurs if `n` is greater than or equal to the amount of remaining values.
+    #[inline]
+    #[must_use]
+    fn nth_value_back(&mut self, n: usize) -> T { self.nth_back(n).unwrap() }
+}
+
+impl<T, U: DoubleEndedIterator<Item = T>> NextValueBack<T> for U {}
+
+//=======================================================================//
+
+/// A trait for peekable iterators to get the next value only if it satisfies a condition.
+pub trait PeekableValue<T>
+where
+    Self: Iterator<Item = T>
+{
+    /// Returns the next value if it satisfies `pred`, otherwise leaves it in the iterator and
+    /// returns None.
+    #[must_use]
+    fn next_value_if<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> bool;
+}
+
+impl<T, I: Iterator<Item = T>> PeekableValue<T> for Peekable<I>
+{
+    #[inline]
+    fn next_value_if<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> bool
+    {
+        self.next_if(pred)
+    }
+}
+
+//=======================================================================//
+
+/// A trait for iterators of [`Result`]s to split their [`Ok`] and [`Err`] values.
+pub trait PartitionResult<U, E>
+where
+    Self: Iterator<Item = Result<U, E>>
+{
+    /// Consumes the iterator and splits its [`Ok`] and [`Err`] values into two [`Vec`]s.
+    #[inline]
+    #[must_use]
+    fn partition_result(self) -> (Vec<U>, Vec<E>)
+    where
+        Self: Sized
+    {
+        let (mut oks, mut errs) = (Vec::new(), Vec::new());
+
+        for value in self
+        {
+            match value
+            {
+                Ok(value) => oks.push(value),
+                Err(err) => errs.push(err)
+            }
+        }
+
+        (oks, errs)
+    }
+}
+
+impl<U, E, I: Iterator<Item = Result<U, E>>> PartitionResult<U, E> for I {}
+
+//=======================================================================//
+
+/// A trait for iterators of string slices to get the next value unwrapped and trimmed.
+pub trait NextTrimmed<'a>
+where
+    Self: Iterator<Item = &'a str>
+{
+    /// Returns the next unwrapped value without leading and trailing whitespace.
+    /// # Panics
+    /// Panic occurs if the next value is None.
+    #[inline]
+    #[must_use]
+    fn next_trimmed(&mut self) -> &'a str { self.next().unwrap().trim() }
+}
+
+impl<'a, I: Iterator<Item = &'a str>> NextTrimmed<'a> for I {}
+
+//=======================================================================//
+
+/// A trait for iterators of pairs to split them into existing [`Vec`]s.
+pub trait UnzipInto<A, B>
+where
+    Self: Iterator<Item = (A, B)>
+{
+    /// Consumes the iterator and appends the first and second elements of each pair to `a` and
+    /// `b`, respectively.
+    #[inline]
+    fn unzip_into(self, a: &mut Vec<A>, b: &mut Vec<B>)
+    where
+        Self: Sized
+    {
+        for (x, y) in self
+        {
+            a.push(x);
+            b.push(y);
+        }
+    }
 }
 
-impl<T, U: Iterator<Item = T>> NextValue<T> for U {}
+impl<A, B, I: Iterator<Item = (A, B)>> UnzipInto<A, B> for I {}
 
 //=======================================================================//
 // MACROS
@@ -199,14 +740,81 @@ macro_rules! match_or_panic {
     };
 }
 
-//=======================================================================//
-// TYPES
-//
 //=======================================================================//
 
-pub enum ManualItem
-{
-    Regular,
+/// Breaks the loop if the next value of the iterator `$iter` is [`None`]. Otherwise it returns the
+/// contained value.
+/// ```
+/// # use hill_vacuum_shared::next_or_break;
+/// let mut iter = [1, 2, 3].into_iter();
+/// let mut sum = 0;
+///
+/// loop
+/// {
+///     sum += next_or_break!(iter);
+/// }
+///
+/// assert_eq!(sum, 6);
+/// ```
+#[macro_export]
+macro_rules! next_or_break {
+    ($iter:expr) => {
+        match $iter.next()
+        {
+            Some(value) => value,
+            None => break
+        }
+    };
+
+    ($iter:expr, $label:tt) => {
+        match $iter.next()
+        {
+            Some(value) => value,
+            None => break $label
+        }
+    };
+}
+
+//=======================================================================//
+// TYPES
+//
+//=======================================================================//
+
+/// Error returned when an iterator contains more values than allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TooMany;
+
+//=======================================================================//
+
+/// An iterator whose next value can be peeked and immediately unwrapped.
+pub struct PeekValueIter<I: Iterator>(Peekable<I>);
+
+impl<I: Iterator> Iterator for PeekValueIter<I>
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> { self.0.next() }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl<I: Iterator> PeekValueIter<I>
+{
+    /// Returns a reference to the next unwrapped value without advancing the iterator.
+    /// # Panics
+    /// Panic occurs if the next value is None.
+    #[inline]
+    #[must_use]
+    pub fn peek_value(&mut self) -> &I::Item { self.0.peek().unwrap() }
+}
+
+//=======================================================================//
+
+pub enum ManualItem
+{
+    Regular,
     Tool,
     Texture
 }
@@ -231,11 +839,254 @@ impl From<char> for ManualItem
     }
 }
 
+//=======================================================================//
+
+/// A template string for each kind of [`ManualItem`]. All templates are empty by default.
+#[derive(Clone, Debug, Default)]
+pub struct ManualTemplates
+{
+    /// The template of [`ManualItem::Regular`].
+    regular: String,
+    /// The template of [`ManualItem::Tool`].
+    tool:    String,
+    /// The template of [`ManualItem::Texture`].
+    texture: String
+}
+
+impl ManualTemplates
+{
+    /// Sets the template of [`ManualItem::Regular`].
+    #[inline]
+    #[must_use]
+    pub fn with_regular<S: Into<String>>(mut self, template: S) -> Self
+    {
+        self.regular = template.into();
+        self
+    }
+
+    /// Sets the template of [`ManualItem::Tool`].
+    #[inline]
+    #[must_use]
+    pub fn with_tool<S: Into<String>>(mut self, template: S) -> Self
+    {
+        self.tool = template.into();
+        self
+    }
+
+    /// Sets the template of [`ManualItem::Texture`].
+    #[inline]
+    #[must_use]
+    pub fn with_texture<S: Into<String>>(mut self, template: S) -> Self
+    {
+        self.texture = template.into();
+        self
+    }
+
+    /// Returns the template of `item`.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, item: &ManualItem) -> &str
+    {
+        match item
+        {
+            ManualItem::Regular => &self.regular,
+            ManualItem::Tool => &self.tool,
+            ManualItem::Texture => &self.texture
+        }
+    }
+}
+
 //=======================================================================//
 // FUNCTIONS
 //
 //=======================================================================//
 
+/// Returns the bounds of [`TEXTURE_HEIGHT_RANGE`] as a `(start, end)` tuple.
+#[inline]
+#[must_use]
+pub const fn texture_height_bounds() -> (i8, i8)
+{
+    (*TEXTURE_HEIGHT_RANGE.start(), *TEXTURE_HEIGHT_RANGE.end())
+}
+
+//=======================================================================//
+
+/// Whether all `heights` are within [`TEXTURE_HEIGHT_RANGE`].
+#[inline]
+#[must_use]
+pub fn all_texture_heights_valid(heights: &[i8]) -> bool
+{
+    heights.iter().all(|h| TEXTURE_HEIGHT_RANGE.contains(h))
+}
+
+//=======================================================================//
+
+/// Returns the label of the texture draw height `h`, which is [`TEXTURE_HEIGHT_FLOOR_LABEL`] or
+/// [`TEXTURE_HEIGHT_CEILING_LABEL`] at the ends of [`TEXTURE_HEIGHT_RANGE`] and the number
+/// otherwise.
+#[inline]
+#[must_use]
+pub fn texture_height_label(h: i8) -> String
+{
+    let (floor, ceiling) = texture_height_bounds();
+
+    if h == floor
+    {
+        TEXTURE_HEIGHT_FLOOR_LABEL.to_owned()
+    }
+    else if h == ceiling
+    {
+        TEXTURE_HEIGHT_CEILING_LABEL.to_owned()
+    }
+    else
+    {
+        h.to_string()
+    }
+}
+
+//=======================================================================//
+
+/// Returns the [`Vec`] of the values returned by `f` called on each pair of consecutive elements of
+/// `slice`, including the pair made of the last and first elements.
+#[inline]
+#[must_use]
+pub fn map_wrapping_pairs<T, U, F>(slice: &[T], mut f: F) -> Vec<U>
+where
+    F: FnMut(&T, &T) -> U
+{
+    slice
+        .iter()
+        .zip(slice.iter().cycle().skip(1))
+        .map(|(a, b)| f(a, b))
+        .collect()
+}
+
+//=======================================================================//
+
+/// Returns the [`Vec`] of the values returned by `f` called on each wrapping triplet of consecutive
+/// elements of `slice`, in the same order as [`iterate_slice_in_triplets`].
+/// # Panics
+/// Panic occurs if `slice` has less than two elements.
+#[inline]
+#[must_use]
+pub fn map_wrapping_triplets<T, U, F>(slice: &[T], mut f: F) -> Vec<U>
+where
+    F: FnMut(&T, &T, &T) -> U
+{
+    assert!(slice.len() >= 2, "Slice has less than two elements.");
+    let mut values = Vec::with_capacity(slice.len());
+
+    iterate_slice_in_triplets!(i, j, k, slice.len(), {
+        values.push(f(&slice[i], &slice[j], &slice[k]));
+    });
+
+    values
+}
+
+//=======================================================================//
+
+/// Returns a copy of `s` where every run of three or more consecutive newlines is collapsed into
+/// two. Both `\n` and `\r\n` line endings are considered newlines.
+#[inline]
+#[must_use]
+pub fn collapse_blank_lines(s: &str) -> String
+{
+    let mut string = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    let mut newlines = 0;
+
+    while let Some(c) = chars.next()
+    {
+        let crlf = c == '\r' && chars.next_if_eq(&'\n').is_some();
+
+        if c != '\n' && !crlf
+        {
+            newlines = 0;
+            string.push(c);
+            continue;
+        }
+
+        newlines += 1;
+
+        if newlines > 2
+        {
+            continue;
+        }
+
+        string.push_str(if crlf { "\r\n" } else { "\n" });
+    }
+
+    string
+}
+
+//=======================================================================//
+
+/// Returns the full extension chain of the file name of `path`, that is everything after its first
+/// dot (`map.hv.bak` returns `hv.bak`). Like [`Path::extension`], a leading dot is not considered.
+#[inline]
+#[must_use]
+pub fn full_extension(path: &Path) -> Option<String>
+{
+    let name = path.file_name()?.to_str()?;
+    let skip = name.chars().next()?.len_utf8();
+    let index = skip + name[skip..].find('.')? + 1;
+    let extension = &name[index..];
+    (!extension.is_empty()).then(|| extension.to_owned())
+}
+
+//=======================================================================//
+
+/// Returns an iterator that alternately yields the values of `a` and `b`, starting from `a`. When
+/// one of the two is exhausted the remaining values of the other are yielded.
+#[inline]
+pub fn interleave<T, A, B>(a: A, b: B) -> impl Iterator<Item = T>
+where
+    A: Iterator<Item = T>,
+    B: Iterator<Item = T>
+{
+    let (mut a, mut b) = (a.fuse(), b.fuse());
+    let mut a_turn = true;
+
+    std::iter::from_fn(move || {
+        let value = if a_turn
+        {
+            a.next().or_else(|| b.next())
+        }
+        else
+        {
+            b.next().or_else(|| a.next())
+        };
+
+        a_turn = !a_turn;
+        value
+    })
+}
+
+//=======================================================================//
+
+/// Converts `v` to [`i8`], returning [`i8::MIN`] or [`i8::MAX`] if it does not fit.
+#[inline]
+#[must_use]
+pub fn saturating_i8(v: i32) -> i8
+{
+    i8::try_from(v).unwrap_or(if v < 0 { i8::MIN } else { i8::MAX })
+}
+
+//=======================================================================//
+
+/// Returns an iterator yielding `n` references to the elements of `slice`, starting over from the
+/// first element after the last one.
+/// # Panics
+/// Panic occurs if `slice` is empty and `n` is greater than zero.
+#[inline]
+pub fn cycle_take<T>(slice: &[T], n: usize) -> impl Iterator<Item = &T>
+{
+    assert!(n == 0 || !slice.is_empty(), "Cannot cycle an empty slice.");
+    (0..n).map(move |i| &slice[i % slice.len()])
+}
+
+//=======================================================================//
+
 #[allow(clippy::missing_panics_doc)]
 #[inline]
 pub fn process_docs<S, N, P, E>(
@@ -308,3 +1159,510 @@ where
 
     string
 }
+
+//=======================================================================//
+// TESTS
+//
+//=======================================================================//
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn full_extension_chains()
+    {
+        assert_eq!(full_extension(Path::new("map.hv")).as_deref(), Some("hv"));
+        assert_eq!(full_extension(Path::new("maps/map.hv.bak")).as_deref(), Some("hv.bak"));
+        assert_eq!(full_extension(Path::new(".hidden")), None);
+        assert_eq!(full_extension(Path::new(".hidden.hv")).as_deref(), Some("hv"));
+        assert_eq!(full_extension(Path::new("map")), None);
+        assert_eq!(full_extension(Path::new("é.hv")).as_deref(), Some("hv"));
+        assert_eq!(full_extension(Path::new("日本.hv")).as_deref(), Some("hv"));
+        assert_eq!(full_extension(Path::new("éa.hv.bak")).as_deref(), Some("hv.bak"));
+    }
+
+    #[test]
+    fn map_wrapping_triplets_quad()
+    {
+        assert_eq!(map_wrapping_triplets(&[1, 2, 3, 4], |a, b, c| a * 100 + b * 10 + c), [
+            341, 412, 123, 234
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "less than two elements")]
+    fn map_wrapping_triplets_empty()
+    {
+        _ = map_wrapping_triplets(&[] as &[i32], |a, b, c| a + b + c);
+    }
+
+    #[test]
+    #[should_panic(expected = "less than two elements")]
+    fn map_wrapping_triplets_single() { _ = map_wrapping_triplets(&[1], |a, b, c| a + b + c); }
+
+    #[test]
+    fn collapse_blank_lines_runs()
+    {
+        assert_eq!(collapse_blank_lines("a\n\n\n\nb\nc\n\n\nd\n\ne"), "a\n\nb\nc\n\nd\n\ne");
+        assert_eq!(collapse_blank_lines("a\r\n\r\n\r\n\r\nb\r\nc"), "a\r\n\r\nb\r\nc");
+        assert_eq!(collapse_blank_lines("a\r\rb\r\n\n\nc"), "a\r\rb\r\n\nc");
+        assert_eq!(collapse_blank_lines("abc"), "abc");
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn next_value_logged_traces_target()
+    {
+        use std::sync::Mutex;
+
+        struct CaptureLogger(Mutex<Vec<(String, String)>>);
+
+        impl log::Log for CaptureLogger
+        {
+            fn enabled(&self, _: &log::Metadata) -> bool { true }
+
+            fn log(&self, record: &log::Record)
+            {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((record.target().to_owned(), record.args().to_string()));
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        assert_eq!([7, 8].into_iter().next_value_logged("hv::parse"), 7);
+        assert_eq!(*LOGGER.0.lock().unwrap(), [("hv::parse".to_owned(), "7".to_owned())]);
+    }
+
+    #[test]
+    fn texture_height_bounds_tuple()
+    {
+        assert_eq!(texture_height_bounds(), (0, 20));
+    }
+
+    #[test]
+    fn for_each_value_all_ok()
+    {
+        let mut sum = 0;
+        assert_eq!(
+            [1, 2, 3].into_iter().for_each_value(|v| {
+                sum += v;
+                Ok::<(), ()>(())
+            }),
+            Ok(())
+        );
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn for_each_value_early_error()
+    {
+        let mut iter = [1, 2, 3, 4].into_iter();
+        let mut visited = Vec::new();
+        assert_eq!(
+            iter.for_each_value(|v| {
+                visited.push(v);
+                if v == 2
+                {
+                    Err(v)
+                }
+                else
+                {
+                    Ok(())
+                }
+            }),
+            Err(2)
+        );
+        assert_eq!(visited, [1, 2]);
+        assert_eq!(iter.next(), Some(3));
+    }
+
+    #[test]
+    fn next_value_if_matches()
+    {
+        let mut iter = [1, 2].into_iter().peekable();
+        assert_eq!(iter.next_value_if(|v| *v == 1), Some(1));
+        assert_eq!(iter.next(), Some(2));
+    }
+
+    #[test]
+    fn next_value_if_leaves()
+    {
+        let mut iter = [1, 2].into_iter().peekable();
+        assert_eq!(iter.next_value_if(|v| *v == 2), None);
+        assert_eq!(iter.next(), Some(1));
+    }
+
+    #[test]
+    fn map_wrapping_pairs_triangle()
+    {
+        assert_eq!(map_wrapping_pairs(&[1, 2, 4], |a, b| b - a), [1, 2, -3]);
+        assert!(map_wrapping_pairs(&[] as &[i32], |a, b| a + b).is_empty());
+    }
+
+    #[test]
+    fn last_value_single_and_multi()
+    {
+        assert_eq!([1].into_iter().last_value(), 1);
+        assert_eq!([1, 2, 3].into_iter().last_value(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn last_value_empty() { _ = std::iter::empty::<i32>().last_value(); }
+
+    #[test]
+    fn counts_by_parity()
+    {
+        let counts = (0..7).counts_by(|v| v % 2 == 0);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&true], 4);
+        assert_eq!(counts[&false], 3);
+    }
+
+    #[test]
+    fn next_value_expect_eq_match_and_mismatch()
+    {
+        let mut iter = ["brush", "thing"].into_iter();
+        assert_eq!(iter.next_value_expect_eq("brush", "bad"), Ok(()));
+        assert_eq!(iter.next_value_expect_eq("brush", "bad"), Err("bad"));
+        assert_eq!(iter.next_value_expect_eq("brush", "empty"), Err("empty"));
+    }
+
+    #[test]
+    fn at_most_one_counts()
+    {
+        assert_eq!(std::iter::empty::<i32>().at_most_one(), Ok(None));
+        assert_eq!([1].into_iter().at_most_one(), Ok(Some(1)));
+        assert_eq!([1, 2].into_iter().at_most_one(), Err(TooMany));
+    }
+
+    #[test]
+    fn manual_templates_per_kind()
+    {
+        let templates = ManualTemplates::default().with_tool("tool").with_texture("texture");
+        assert_eq!(templates.get(&ManualItem::Tool), "tool");
+        assert_eq!(templates.get(&ManualItem::Texture), "texture");
+        assert_eq!(templates.get(&ManualItem::Regular), "");
+        assert_eq!(templates.with_regular("regular").get(&ManualItem::Regular), "regular");
+    }
+
+    #[test]
+    fn find_duplicate_present_and_absent()
+    {
+        assert_eq!([3, 1, 4, 1, 5, 3].into_iter().find_duplicate(), Some(1));
+        assert_eq!([1, 2, 3].into_iter().find_duplicate(), None);
+    }
+
+    #[test]
+    fn next_value_try_into_cast()
+    {
+        let mut iter = [7u32, 70_000].into_iter();
+        assert_eq!(iter.next_value_try_into::<u16>(), Ok(7));
+        assert!(iter.next_value_try_into::<u16>().is_err());
+    }
+
+    #[test]
+    fn min_by_key_value_clear_minimum()
+    {
+        assert_eq!([(0, 5), (1, -2), (2, 3)].into_iter().min_by_key_value(|v| v.1), (1, -2));
+
+        let mut iter: &mut dyn Iterator<Item = i32> = &mut [3, 1, 2].into_iter();
+        assert_eq!((&mut iter).min_by_key_value(|v| *v), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn min_by_key_value_empty() { _ = std::iter::empty::<i32>().min_by_key_value(|v| *v); }
+
+    #[test]
+    fn texture_height_labels()
+    {
+        assert_eq!(texture_height_label(0), TEXTURE_HEIGHT_FLOOR_LABEL);
+        assert_eq!(texture_height_label(20), TEXTURE_HEIGHT_CEILING_LABEL);
+        assert_eq!(texture_height_label(10), "10");
+    }
+
+    #[test]
+    fn interleave_equal_length()
+    {
+        assert_eq!(interleave([1, 3, 5].into_iter(), [2, 4, 6].into_iter()).collect::<Vec<_>>(), [
+            1, 2, 3, 4, 5, 6
+        ]);
+    }
+
+    #[test]
+    fn interleave_unequal_length()
+    {
+        assert_eq!(interleave([1, 3, 5, 7].into_iter(), [2].into_iter()).collect::<Vec<_>>(), [
+            1, 2, 3, 5, 7
+        ]);
+        assert_eq!(interleave([1].into_iter(), [2, 4, 6].into_iter()).collect::<Vec<_>>(), [
+            1, 2, 4, 6
+        ]);
+    }
+
+    #[test]
+    fn next_value_non_default_nonzero()
+    {
+        assert_eq!([3].into_iter().next_value_non_default(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "default value")]
+    fn next_value_non_default_zero() { _ = [0].into_iter().next_value_non_default(); }
+
+    #[test]
+    fn take_while_collect_runs()
+    {
+        assert_eq!([1, 2, 5, 3].into_iter().take_while_collect(|v| *v < 4), [1, 2]);
+        assert_eq!([1, 2, 3].into_iter().take_while_collect(|v| *v < 4), [1, 2, 3]);
+
+        let mut iter = [1, 5, 2].into_iter();
+        assert_eq!(iter.by_ref().take_while_collect(|v| *v < 4), [1]);
+        assert_eq!(iter.next(), Some(2));
+    }
+
+    #[test]
+    fn collect_bounded_counts()
+    {
+        assert_eq!([1, 2, 3].into_iter().collect_bounded(2, 3), Ok(vec![1, 2, 3]));
+        assert_eq!([1].into_iter().collect_bounded(2, 3), Err(1));
+        assert_eq!([1, 2, 3, 4].into_iter().collect_bounded(2, 3), Err(4));
+
+        let iter: &mut dyn Iterator<Item = i32> = &mut [1, 2].into_iter();
+        assert_eq!(iter.collect_bounded(0, 2), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn chunk_by_key_runs()
+    {
+        assert_eq!([1, 1, 2, 3, 3].into_iter().chunk_by_key(|v| *v).collect::<Vec<_>>(), [
+            vec![1, 1],
+            vec![2],
+            vec![3, 3]
+        ]);
+        assert_eq!(std::iter::empty::<i32>().chunk_by_key(|v| *v).count(), 0);
+    }
+
+    #[test]
+    fn next_value_within_range()
+    {
+        let mut iter = [5i8, 21].into_iter();
+        assert_eq!(iter.next_value_within(TEXTURE_HEIGHT_RANGE), Ok(5));
+        assert_eq!(iter.next_value_within(TEXTURE_HEIGHT_RANGE), Err(21));
+    }
+
+    #[test]
+    fn sum_by_key_lengths()
+    {
+        assert_eq!(["a", "bcd", "ef"].into_iter().sum_by_key(str::len), 6);
+    }
+
+    #[test]
+    fn nth_value_back_slice()
+    {
+        let mut iter = [1, 2, 3, 4].iter();
+        assert_eq!(*iter.nth_value_back(0), 4);
+        assert_eq!(*iter.nth_value_back(1), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn nth_value_back_out_of_range() { _ = [1, 2].iter().nth_value_back(2); }
+
+    #[test]
+    fn saturating_i8_values()
+    {
+        assert_eq!(saturating_i8(i32::MAX), i8::MAX);
+        assert_eq!(saturating_i8(i32::MIN), i8::MIN);
+        assert_eq!(saturating_i8(-5), -5);
+        assert_eq!(saturating_i8(127), 127);
+    }
+
+    #[test]
+    fn partition_result_mixed()
+    {
+        let (oks, errs) = ["1", "x", "3", "y"]
+            .into_iter()
+            .map(str::parse::<i32>)
+            .map(|r| r.map_err(|_| ()))
+            .partition_result();
+        assert_eq!(oks, [1, 3]);
+        assert_eq!(errs.len(), 2);
+    }
+
+    #[test]
+    fn running_total_sums()
+    {
+        assert_eq!([1, 2, 3].into_iter().running_total().collect::<Vec<_>>(), [1, 3, 6]);
+    }
+
+    #[test]
+    fn collect_array_or_default_fill()
+    {
+        assert_eq!([1, 2, 3, 4].into_iter().collect_array_or_default::<3>(), [1, 2, 3]);
+        assert_eq!([1].into_iter().collect_array_or_default::<3>(), [1, 0, 0]);
+        assert_eq!(std::iter::empty::<i32>().collect_array_or_default::<2>(), [0, 0]);
+    }
+
+    #[test]
+    fn all_texture_heights_valid_slices()
+    {
+        assert!(all_texture_heights_valid(&[0, 10, 20]));
+        assert!(!all_texture_heights_valid(&[0, 21, 10]));
+        assert!(!all_texture_heights_valid(&[-1]));
+    }
+
+    #[test]
+    fn find_indexed_value_found()
+    {
+        assert_eq!([5, 6, 7].into_iter().find_indexed_value(|v| *v > 5), (1, 6));
+    }
+
+    #[test]
+    #[should_panic]
+    fn find_indexed_value_not_found() { _ = [5, 6].into_iter().find_indexed_value(|v| *v > 6); }
+
+    #[test]
+    fn next_trimmed_padded()
+    {
+        assert_eq!(["  brush \t", "x"].into_iter().next_trimmed(), "brush");
+    }
+
+    #[test]
+    #[should_panic]
+    fn next_trimmed_empty() { _ = std::iter::empty::<&str>().next_trimmed(); }
+
+    #[test]
+    fn unzip_into_appends()
+    {
+        let (mut ids, mut names) = (vec![0], vec!["zero"]);
+        [(1, "one"), (2, "two")].into_iter().unzip_into(&mut ids, &mut names);
+        assert_eq!(ids, [0, 1, 2]);
+        assert_eq!(names, ["zero", "one", "two"]);
+    }
+
+    #[test]
+    fn next_value_checked_passes()
+    {
+        assert_eq!([4].into_iter().next_value_checked(|v| v % 2 == 0, "odd"), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "value is odd")]
+    fn next_value_checked_fails()
+    {
+        _ = [3].into_iter().next_value_checked(|v| v % 2 == 0, "value is odd");
+    }
+
+    #[test]
+    fn collect_until_sentinel()
+    {
+        let mut iter = [1, 2, 0, 3].into_iter();
+        assert_eq!(iter.collect_until(0), [1, 2]);
+        assert_eq!(iter.next(), Some(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn collect_until_missing_sentinel() { _ = [1, 2].into_iter().collect_until(0); }
+
+    #[test]
+    fn exactly_one_value_one()
+    {
+        assert_eq!([1].into_iter().exactly_one_value(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn exactly_one_value_zero() { _ = std::iter::empty::<i32>().exactly_one_value(); }
+
+    #[test]
+    #[should_panic(expected = "more than one value")]
+    fn exactly_one_value_two() { _ = [1, 2].into_iter().exactly_one_value(); }
+
+    #[test]
+    fn collect_pairs_exact_pairs()
+    {
+        let mut iter = [1, 2, 3, 4, 5].into_iter();
+        assert_eq!(iter.collect_pairs_exact(2), [(1, 2), (3, 4)]);
+        assert_eq!(iter.next(), Some(5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn collect_pairs_exact_short() { _ = [1, 2, 3].into_iter().collect_pairs_exact(2); }
+
+    #[test]
+    fn max_value_clear_max()
+    {
+        assert_eq!([3, 20, 7].into_iter().max_value(), 20);
+
+        let iter: &mut dyn Iterator<Item = i32> = &mut [3, 1, 2].into_iter();
+        assert_eq!(iter.max_value(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn max_value_empty() { _ = std::iter::empty::<i32>().max_value(); }
+
+    #[test]
+    fn next_value_at_value()
+    {
+        assert_eq!([1].into_iter().next_value_at(0), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3")]
+    fn next_value_at_panic_index()
+    {
+        let mut iter = [1, 2, 3].into_iter();
+
+        for i in 0..4
+        {
+            _ = iter.next_value_at(i);
+        }
+    }
+
+    #[test]
+    fn run_length_encode_runs()
+    {
+        assert_eq!([1, 1, 1, 2, 3, 3].into_iter().run_length_encode().collect::<Vec<_>>(), [
+            (1, 3),
+            (2, 1),
+            (3, 2)
+        ]);
+    }
+
+    #[test]
+    fn cycle_take_lengths()
+    {
+        let palette = [1, 2, 3];
+        assert_eq!(cycle_take(&palette, 7).copied().collect::<Vec<_>>(), [1, 2, 3, 1, 2, 3, 1]);
+        assert_eq!(cycle_take(&palette, 3).copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(cycle_take(&palette, 2).copied().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(cycle_take(&[] as &[i32], 0).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty slice")]
+    fn cycle_take_empty() { _ = cycle_take(&[] as &[i32], 1); }
+
+    #[test]
+    fn next_value_dyn_compatible()
+    {
+        let iter: &mut dyn NextValue<i32> = &mut [1, 2, 3].into_iter();
+        assert_eq!(iter.next_value(), 1);
+        assert_eq!(iter.max_value(), 3);
+    }
+}